@@ -1,13 +1,23 @@
 mod path_finding {
-    use petgraph::algo::{bellman_ford, dijkstra};
-    use petgraph::graphmap::UnGraphMap;
+    use petgraph::algo::{astar, bellman_ford, dijkstra};
+    use petgraph::csr::Csr;
+    use petgraph::graphmap::{DiGraphMap, UnGraphMap};
     pub use petgraph::Graph;
+    use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+    use petgraph::Directed;
     use std::borrow::Borrow;
-    use std::collections::HashSet;
+    use std::cmp::Ordering;
+    use std::collections::{BinaryHeap, HashMap, HashSet};
     use std::time::Instant;
 
     type Point = (i32, i32);
 
+    // Integer edge costs for the 8-connected grid: 10 for an orthogonal step and
+    // 14 (~10 * sqrt(2)) for a diagonal one, so the octile heuristic below stays
+    // both integral and admissible.
+    const ORTHOGONAL_COST: i32 = 10;
+    const DIAGONAL_COST: i32 = 14;
+
     fn neighbors(p: Point, x_len: i32, y_len: i32) -> Vec<Point> {
         if p.0 < 0 || p.1 < 0 || p.0 >= x_len || p.1 >= y_len {
             panic!("point ({}, {}) is out of bounds of the graph!", p.0, p.1);
@@ -26,8 +36,10 @@ mod path_finding {
             .collect::<Vec<Point>>()
     }
 
-    // Graph specified by having each tile connected to all its neighbors by edges.
-    // Each edge is marked with a boolean to indicate if it blocked.
+    // Graph specified by having each tile connected to all its neighbors by edges,
+    // with no per-tile terrain cost: `ORTHOGONAL_COST` for an orthogonal step and
+    // `DIAGONAL_COST` for a diagonal one. See `build_weighted_tile_graph` for a
+    // version that supports per-tile cost and impassable tiles.
     pub fn build_empty_tile_graph(x_size: i32, y_size: i32) -> UnGraphMap<Point, i32> {
         if x_size < 1 || y_size < 1 {
             panic!(
@@ -44,73 +56,520 @@ mod path_finding {
         for p in points {
             g.add_node(p);
             for n in neighbors(p, x_size, y_size) {
-                g.add_edge(p, n, 1);
+                let cost = if n.0 == p.0 || n.1 == p.1 {
+                    ORTHOGONAL_COST
+                } else {
+                    DIAGONAL_COST
+                };
+                g.add_edge(p, n, cost);
+            }
+        }
+        return g;
+    }
+
+    // Graph over a grid where each tile carries its own movement cost, or is
+    // impassable. `cost(p)` returning `None` omits `p` and every edge touching it
+    // from the graph entirely; `Some(c)` sets the per-tile cost of `p`. An
+    // edge's weight is the average of the cost of its two tiles (rounded up),
+    // multiplied by
+    // `ORTHOGONAL_COST` for an orthogonal move or `DIAGONAL_COST` for a diagonal
+    // one, so routes naturally avoid mud, water, or walls instead of walking
+    // straight through them. Scaling up rather than dividing down keeps the
+    // `DIAGONAL_COST`/`ORTHOGONAL_COST` ratio exact even for small per-tile
+    // costs, where dividing first would round the diagonal penalty away.
+    //
+    // This is a deliberate deviation from a strict "cost of entering a tile"
+    // model: that model is directional (the cost of A -> B needn't equal
+    // B -> A), but `UnGraphMap` can only hold one weight per edge, so it
+    // can't represent two different costs for the two directions of the same
+    // move. Averaging the two tiles' costs is the closest undirected
+    // approximation and keeps every edge symmetric; callers that need true
+    // directional cost (e.g. cheap to approach a tile from one side, pricier
+    // from another) should reach for `build_terrain_graph`'s `DiGraphMap`
+    // instead.
+    pub fn build_weighted_tile_graph(
+        x_size: i32,
+        y_size: i32,
+        cost: impl Fn(Point) -> Option<i32>,
+    ) -> UnGraphMap<Point, i32> {
+        if x_size < 1 || y_size < 1 {
+            panic!(
+                "Invalid values for the graph size x: {}, y: {}",
+                x_size, y_size
+            );
+        }
+        let points = (0..x_size)
+            .map(|x| (0..y_size).map(move |y| (x, y)))
+            .flatten()
+            .collect::<Vec<Point>>();
+
+        let mut g = UnGraphMap::<Point, i32>::new();
+        for p in points {
+            let p_cost = match cost(p) {
+                Some(c) => c,
+                None => continue,
+            };
+            g.add_node(p);
+            for n in neighbors(p, x_size, y_size) {
+                let n_cost = match cost(n) {
+                    Some(c) => c,
+                    None => continue,
+                };
+                let diagonal = n.0 != p.0 && n.1 != p.1;
+                let base = (p_cost + n_cost + 1) / 2;
+                let weight = if diagonal {
+                    base * DIAGONAL_COST
+                } else {
+                    base * ORTHOGONAL_COST
+                };
+                g.add_edge(p, n, weight);
+            }
+        }
+        return g;
+    }
+
+    // Directed graph over a grid with a height field, for planning across real
+    // 2.5D terrain where climbing is harder than descending. An edge from `a`
+    // to `b` is only added when `height(b) - height(a) <= max_climb`, so a
+    // cliff blocks movement uphill while still allowing the agent to drop back
+    // down it; `slope_penalty` adds to the horizontal movement cost based on
+    // the elevation change, e.g. to make steep climbs pricier than shallow
+    // ones. Negative penalties are clamped so an edge never gets a weight
+    // below the flat horizontal cost.
+    pub fn build_terrain_graph(
+        x_size: i32,
+        y_size: i32,
+        height: impl Fn(Point) -> i32,
+        max_climb: i32,
+        slope_penalty: impl Fn(i32) -> i32,
+    ) -> DiGraphMap<Point, i32> {
+        if x_size < 1 || y_size < 1 {
+            panic!(
+                "Invalid values for the graph size x: {}, y: {}",
+                x_size, y_size
+            );
+        }
+        let points = (0..x_size)
+            .map(|x| (0..y_size).map(move |y| (x, y)))
+            .flatten()
+            .collect::<Vec<Point>>();
+
+        let mut g = DiGraphMap::<Point, i32>::new();
+        for p in points {
+            g.add_node(p);
+            for n in neighbors(p, x_size, y_size) {
+                let climb = height(n) - height(p);
+                if climb > max_climb {
+                    continue;
+                }
+                let diagonal = n.0 != p.0 && n.1 != p.1;
+                let horizontal_cost = if diagonal {
+                    DIAGONAL_COST
+                } else {
+                    ORTHOGONAL_COST
+                };
+                let weight = horizontal_cost + slope_penalty(climb).max(0);
+                g.add_edge(p, n, weight);
             }
         }
         return g;
     }
 
+    // Admissible, consistent estimate of the remaining cost to `goal` on the
+    // 8-connected grid: octile distance scaled by the given per-step units, so
+    // it never overestimates as long as no orthogonal edge is cheaper than
+    // `orthogonal_unit`, no diagonal edge is cheaper than `diagonal_unit`, and
+    // `orthogonal_unit <= diagonal_unit <= 2 * orthogonal_unit`.
+    fn octile_heuristic(from: Point, goal: Point, orthogonal_unit: i32, diagonal_unit: i32) -> i32 {
+        let dx = (goal.0 - from.0).abs();
+        let dy = (goal.1 - from.1).abs();
+        let straight = (dx - dy).abs();
+        let diagonal = dx.min(dy);
+        straight * orthogonal_unit + diagonal * diagonal_unit
+    }
+
+    // The cheapest orthogonal and diagonal edge weight actually present among
+    // `edges`. `find_path_astar`/`find_path_astar_csr` use these, rather than
+    // the hard-coded `ORTHOGONAL_COST`/`DIAGONAL_COST`, to scale the octile
+    // heuristic: on `build_weighted_tile_graph` output some tiles may be
+    // cheaper than `ORTHOGONAL_COST`, and a heuristic scaled to the fixed
+    // constants would overestimate the remaining cost there and stop being
+    // admissible. Falls back to the fixed constants for a kind of edge the
+    // graph has none of. The orthogonal unit is then capped at the diagonal
+    // unit and the diagonal unit at twice the orthogonal one, since two
+    // diagonal steps can replace a straight move and two orthogonal steps a
+    // diagonal one.
+    fn min_edge_units(edges: impl Iterator<Item = (Point, Point, i32)>) -> (i32, i32) {
+        let mut orthogonal_unit = i32::MAX;
+        let mut diagonal_unit = i32::MAX;
+        for (a, b, weight) in edges {
+            if a.0 == b.0 || a.1 == b.1 {
+                orthogonal_unit = orthogonal_unit.min(weight);
+            } else {
+                diagonal_unit = diagonal_unit.min(weight);
+            }
+        }
+        let orthogonal_unit = if orthogonal_unit == i32::MAX {
+            ORTHOGONAL_COST
+        } else {
+            orthogonal_unit
+        };
+        let diagonal_unit = if diagonal_unit == i32::MAX {
+            DIAGONAL_COST
+        } else {
+            diagonal_unit
+        };
+        let orthogonal_unit = orthogonal_unit.min(diagonal_unit);
+        let diagonal_unit = diagonal_unit.min(orthogonal_unit.saturating_mul(2));
+        (orthogonal_unit, diagonal_unit)
+    }
+
+    // A* over the grid graph built by `build_empty_tile_graph` or
+    // `build_weighted_tile_graph`, using an octile heuristic scaled to the
+    // graph's own cheapest orthogonal/diagonal edges so it stays admissible
+    // for weighted terrain, not just the uniform-cost grid. Explores far fewer
+    // nodes than a uniform-cost Dijkstra search on the large grids
+    // `find_path_dfs` benchmarks.
+    pub fn find_path_astar(
+        g: &UnGraphMap<Point, i32>,
+        start: Point,
+        goal: Point,
+    ) -> Option<(i32, Vec<Point>)> {
+        let (orthogonal_unit, diagonal_unit) =
+            min_edge_units(g.all_edges().map(|(a, b, &w)| (a, b, w)));
+        astar(
+            g,
+            start,
+            |n| n == goal,
+            |(_, _, &cost)| cost,
+            |n| octile_heuristic(n, goal, orthogonal_unit, diagonal_unit),
+        )
+    }
+
+    // Flattens a `UnGraphMap` into a Compressed Sparse Row adjacency structure:
+    // one contiguous, sorted-neighbor-range `Vec` rather than `GraphMap`'s
+    // per-node hash sets. The CSR is built `Directed` with both directions of
+    // every undirected edge listed explicitly, because `Csr::add_edge` is
+    // O(|V|) per call (it rewrites the row index every time), which makes
+    // incremental construction O(|V| * |E|); `Csr::from_sorted_edges` instead
+    // takes one already-sorted edge list and builds the whole structure in a
+    // single O(|V| + |E|) pass. Repeated A*/Dijkstra queries over the same
+    // static map then walk this cache-friendly layout instead of re-hashing
+    // `Point` on every traversal. Returns the CSR graph alongside
+    // index<->`Point` lookup tables, since the CSR's node ids are dense
+    // `u32`s rather than the original `Point`s.
+    pub fn to_csr(
+        g: &UnGraphMap<Point, i32>,
+    ) -> (Csr<(), i32, Directed>, Vec<Point>, HashMap<Point, u32>) {
+        let mut index_to_point: Vec<Point> = g.nodes().collect();
+        index_to_point.sort();
+
+        let mut point_to_index = HashMap::with_capacity(index_to_point.len());
+        for (i, &p) in index_to_point.iter().enumerate() {
+            point_to_index.insert(p, i as u32);
+        }
+
+        let mut edges: Vec<(u32, u32, i32)> = Vec::with_capacity(g.edge_count() * 2);
+        for (a, b, &weight) in g.all_edges() {
+            let (ia, ib) = (point_to_index[&a], point_to_index[&b]);
+            edges.push((ia, ib, weight));
+            edges.push((ib, ia, weight));
+        }
+        edges.sort_unstable_by_key(|&(a, b, _)| (a, b));
+
+        let mut csr = Csr::<(), i32, Directed>::from_sorted_edges(&edges)
+            .expect("reciprocal edges sorted by (source, target) are never rejected");
+        while csr.node_count() < index_to_point.len() {
+            csr.add_node(());
+        }
+
+        (csr, index_to_point, point_to_index)
+    }
+
+    // A* over a CSR graph produced by `to_csr`, for callers that run many
+    // queries against the same static map and want to pay the flattening cost
+    // once rather than per query.
+    pub fn find_path_astar_csr(
+        csr: &Csr<(), i32, Directed>,
+        index_to_point: &[Point],
+        point_to_index: &HashMap<Point, u32>,
+        start: Point,
+        goal: Point,
+    ) -> Option<(i32, Vec<Point>)> {
+        let start_index = *point_to_index.get(&start)?;
+        let goal_index = *point_to_index.get(&goal)?;
+        let (orthogonal_unit, diagonal_unit) = min_edge_units(csr.edge_references().map(|edge| {
+            (
+                index_to_point[edge.source() as usize],
+                index_to_point[edge.target() as usize],
+                *edge.weight(),
+            )
+        }));
+
+        astar(
+            csr,
+            start_index,
+            |n| n == goal_index,
+            |edge| *edge.weight(),
+            |n| octile_heuristic(index_to_point[n as usize], goal, orthogonal_unit, diagonal_unit),
+        )
+        .map(|(cost, path)| {
+            (
+                cost,
+                path.into_iter().map(|n| index_to_point[n as usize]).collect(),
+            )
+        })
+    }
+
     fn _neighbors_3x3(p: Point) -> Vec<Point> {
         neighbors(p, 3, 3)
     }
 
-    struct Line {
-        first: Point,
-        second: Point,
+    // Every grid cell that the segment from `a` to `b` passes through (a
+    // "supercover" line): where the segment crosses from one row or column
+    // into the next, both cells on either side of that boundary are included.
+    // Where it passes exactly through a cell corner it steps diagonally, so the
+    // two cells that only touch that corner are not included. Handles vertical
+    // and right-to-left segments and always ends at `b`.
+    fn supercover_line(a: Point, b: Point) -> Vec<Point> {
+        let dx = b.0 - a.0;
+        let dy = b.1 - a.1;
+        let nx = dx.abs();
+        let ny = dy.abs();
+        let sign_x = if dx > 0 { 1 } else { -1 };
+        let sign_y = if dy > 0 { 1 } else { -1 };
+
+        let mut p = a;
+        let mut points = vec![p];
+        let mut ix = 0;
+        let mut iy = 0;
+        while ix < nx || iy < ny {
+            let decision = (1 + 2 * ix) * ny - (1 + 2 * iy) * nx;
+            if decision == 0 {
+                p.0 += sign_x;
+                p.1 += sign_y;
+                ix += 1;
+                iy += 1;
+            } else if decision < 0 {
+                p.0 += sign_x;
+                ix += 1;
+            } else {
+                p.1 += sign_y;
+                iy += 1;
+            }
+            points.push(p);
+        }
+        points
     }
 
-    struct Path {
-        line: Line,
-        curr_x: i32,
+    // True when every grid cell the straight segment `a`-`b` passes through is
+    // present in `g`, i.e. none of them is a blocked/omitted tile. Used by
+    // Theta* below to shortcut A*'s parent pointers into straight shots.
+    pub fn line_of_sight(g: &UnGraphMap<Point, i32>, a: Point, b: Point) -> bool {
+        supercover_line(a, b)
+            .into_iter()
+            .all(|step| g.contains_node(step))
     }
 
-    impl Iterator for Path {
-        type Item = Point;
+    // The real movement cost of walking the straight segment `a`-`b`, summing
+    // the graph's own edge weight for each hop of its supercover line instead
+    // of the segment's Euclidean length. `None` if any hop isn't actually an
+    // edge in `g` (e.g. a blocked tile), mirroring `line_of_sight`. Used by
+    // Theta* so a line-of-sight shortcut respects per-tile terrain cost the
+    // same way the staircase path it replaces would.
+    fn line_of_sight_cost(g: &UnGraphMap<Point, i32>, a: Point, b: Point) -> Option<f64> {
+        supercover_line(a, b)
+            .windows(2)
+            .map(|step| g.edge_weight(step[0], step[1]).map(|&cost| cost as f64))
+            .sum()
+    }
 
-        fn next(&mut self) -> Option<Self::Item> {
-            return if self.curr_x < self.line.second.0 {
-                let line_angle_to_origin = ((self.line.second.1 - self.line.first.1) as f64
-                    / (self.line.second.0 - self.line.first.0) as f64)
-                    .atan();
-                let dx = self.curr_x - self.line.first.0;
-                let dy = dx as f64 * line_angle_to_origin.sin();
-                let y_at_this_point = self.line.first.1 + (dy as i32);
-                let next: Point = (self.curr_x, y_at_this_point);
-                self.curr_x = self.curr_x + 1;
-                Option::Some(next)
-            } else if self.curr_x == self.line.second.0 {
-                Option::Some(self.line.second)
-            } else {
-                Option::None
-            };
+    fn euclidean(a: Point, b: Point) -> f64 {
+        (((a.0 - b.0).pow(2) + (a.1 - b.1).pow(2)) as f64).sqrt()
+    }
+
+    // Open-list entry for Theta*, ordered by ascending `f_score` (min-heap via
+    // `BinaryHeap`, which is otherwise a max-heap).
+    #[derive(Copy, Clone, PartialEq)]
+    struct ThetaCandidate {
+        f_score: f64,
+        point: Point,
+    }
+
+    impl Eq for ThetaCandidate {}
+
+    impl Ord for ThetaCandidate {
+        fn cmp(&self, other: &Self) -> Ordering {
+            other
+                .f_score
+                .partial_cmp(&self.f_score)
+                .unwrap_or(Ordering::Equal)
         }
     }
 
-    fn min_distance_from_line(point: Point, line: Line) -> f64 {
-        let line_angle_to_origin =
-            ((line.second.1 - line.first.1) as f64 / (line.second.0 - line.first.0) as f64).atan();
-        let point_angle_to_origin = (point.1 as f64 / point.0 as f64).atan();
-        let distance_origin_to_point = ((point.0 * point.0 + point.1 * point.1) as f64).sqrt();
-        return ((point_angle_to_origin - line_angle_to_origin).sin() * distance_origin_to_point)
-            .abs();
+    impl PartialOrd for ThetaCandidate {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
     }
 
-    fn collision_between(p1: Point, p2: Point, g: UnGraphMap<Point, i32>) -> bool {
-        let path: Path = Path {
-            line: Line {
-                first: p1.clone(),
-                second: p2.clone(),
-            },
-            curr_x: p1.0,
-        };
+    // Any-angle pathfinding: A* with Theta*'s parent-propagation rule. When
+    // relaxing neighbor `s_prime` of the current node `s`, a clear line of
+    // sight from `parent(s)` straight to `s_prime` lets us skip `s` entirely,
+    // costing the shortcut as the sum of the real per-tile movement cost
+    // along that line (via `line_of_sight_cost`) rather than its Euclidean
+    // length, so the final path is a short list of turn points rather than a
+    // staircase of 8-connected grid steps. The heuristic stays plain
+    // Euclidean distance to `goal`, which is admissible while every edge costs
+    // at least its Euclidean length: true on `build_empty_tile_graph` and on
+    // `build_weighted_tile_graph` with per-tile costs of 1 or more (at least
+    // `ORTHOGONAL_COST` per orthogonal and `DIAGONAL_COST` per diagonal step),
+    // but not with per-tile costs of 0 or below.
+    //
+    // The returned cost always matches what the returned path actually costs
+    // to walk, but on a `build_weighted_tile_graph` with non-uniform per-tile
+    // cost the path itself isn't guaranteed shortest: a line-of-sight
+    // shortcut assumes the straight line between two points is at least as
+    // cheap as any detour between them, which only holds when movement cost
+    // is uniform (as on `build_empty_tile_graph`, or obstacles-only weighted
+    // terrain). Use `find_path_astar` when optimality on uneven terrain
+    // matters more than the any-angle, fewer-turn-points path shape.
+    pub fn find_path_theta_star(
+        g: &UnGraphMap<Point, i32>,
+        start: Point,
+        goal: Point,
+    ) -> Option<(f64, Vec<Point>)> {
+        let mut open = BinaryHeap::new();
+        let mut g_score: HashMap<Point, f64> = HashMap::new();
+        let mut parent: HashMap<Point, Point> = HashMap::new();
+        let mut closed: HashSet<Point> = HashSet::new();
 
-        for step in path {
-            if !g.contains_node(step) {
-                return true;
+        g_score.insert(start, 0.0);
+        parent.insert(start, start);
+        open.push(ThetaCandidate {
+            f_score: euclidean(start, goal),
+            point: start,
+        });
+
+        while let Some(ThetaCandidate { point: s, .. }) = open.pop() {
+            if s == goal {
+                let mut path = vec![s];
+                let mut current = s;
+                while parent[&current] != current {
+                    current = parent[&current];
+                    path.push(current);
+                }
+                path.reverse();
+                return Some((g_score[&s], path));
+            }
+            if !closed.insert(s) {
+                continue;
+            }
+
+            let s_parent = parent[&s];
+            for neighbor in g.neighbors(s) {
+                if closed.contains(&neighbor) {
+                    continue;
+                }
+
+                let (candidate_parent, tentative_g) = match line_of_sight_cost(g, s_parent, neighbor)
+                {
+                    Some(cost) => (s_parent, g_score[&s_parent] + cost),
+                    None => {
+                        let edge_cost =
+                            *g.edge_weight(s, neighbor).expect("neighbor came from g.neighbors(s)")
+                                as f64;
+                        (s, g_score[&s] + edge_cost)
+                    }
+                };
+
+                if tentative_g < *g_score.get(&neighbor).unwrap_or(&f64::INFINITY) {
+                    g_score.insert(neighbor, tentative_g);
+                    parent.insert(neighbor, candidate_parent);
+                    open.push(ThetaCandidate {
+                        f_score: tentative_g + euclidean(neighbor, goal),
+                        point: neighbor,
+                    });
+                }
             }
         }
 
-        return false;
+        None
+    }
+
+    // Enumerates every simple shortest path from `start` to `goal`, not just one
+    // of them. Runs Dijkstra once to get each node's shortest distance, then
+    // treats the "tight" edges (u, v) where `dist[u] + weight(u, v) == dist[v]`
+    // as a predecessor DAG and DFS-backtracks from `goal` through it, so only
+    // optimal routes are ever materialized. `cap` bounds how many paths are
+    // collected, since the DAG can branch combinatorially on open grids.
+    pub fn all_shortest_paths(
+        g: &UnGraphMap<Point, i32>,
+        start: Point,
+        goal: Point,
+        cap: Option<usize>,
+    ) -> Vec<Vec<Point>> {
+        if start == goal {
+            return vec![vec![start]];
+        }
+
+        let dist = dijkstra(g, start, Some(goal), |(_, _, &weight)| weight);
+        if !dist.contains_key(&goal) {
+            return vec![];
+        }
+
+        let mut results = Vec::new();
+        let mut path = Vec::new();
+        let mut visited = HashSet::new();
+        collect_shortest_paths(g, &dist, start, goal, &mut path, &mut visited, &mut results, cap);
+        results
+    }
+
+    fn collect_shortest_paths(
+        g: &UnGraphMap<Point, i32>,
+        dist: &HashMap<Point, i32>,
+        start: Point,
+        current: Point,
+        path: &mut Vec<Point>,
+        visited: &mut HashSet<Point>,
+        results: &mut Vec<Vec<Point>>,
+        cap: Option<usize>,
+    ) {
+        if cap.is_some_and(|limit| results.len() >= limit) {
+            return;
+        }
+
+        path.push(current);
+        if current == start {
+            let mut found = path.clone();
+            found.reverse();
+            results.push(found);
+        } else {
+            visited.insert(current);
+            for predecessor in g.neighbors(current) {
+                // A zero-weight edge makes both of its directions "tight"
+                // (dist[u] + 0 == dist[v] and vice versa when dist[u] ==
+                // dist[v]), which would otherwise turn the predecessor DAG
+                // into a 2-cycle. Skipping nodes already on the current path
+                // keeps every collected path simple and keeps the DFS
+                // terminating regardless of zero-cost tiles.
+                if visited.contains(&predecessor) {
+                    continue;
+                }
+                let is_tight = match (dist.get(&predecessor), g.edge_weight(current, predecessor)) {
+                    (Some(&d), Some(&weight)) => d + weight == dist[&current],
+                    _ => false,
+                };
+                if is_tight {
+                    collect_shortest_paths(g, dist, start, predecessor, path, visited, results, cap);
+                    if cap.is_some_and(|limit| results.len() >= limit) {
+                        break;
+                    }
+                }
+            }
+            visited.remove(&current);
+        }
+        path.pop();
     }
 
     #[test]
@@ -132,6 +591,67 @@ mod path_finding {
         }
     }
 
+    #[test]
+    fn find_path_astar_benchmark() {
+        // Same grid sizes as `find_path_dfs`: 128, 256 and 512 tiles per side.
+        for graph_size_power in (7..10) {
+            let now = Instant::now();
+            let base: i32 = 2;
+            let graph_size = base.pow(graph_size_power);
+            let house: petgraph::graphmap::GraphMap<(i32, i32), i32, petgraph::Undirected> =
+                build_empty_tile_graph(graph_size, graph_size);
+            find_path_astar(&house, (0, 0), (graph_size - 1, graph_size - 1));
+            println!("graph size: {}, milliseconds elapsed: {}", graph_size, now.elapsed().as_millis());
+        }
+    }
+
+    #[test]
+    fn find_path_astar_straight_line() {
+        let g = build_empty_tile_graph(4, 1);
+        let (cost, path) = find_path_astar(&g, (0, 0), (3, 0)).unwrap();
+        assert_eq!(cost, 3 * ORTHOGONAL_COST);
+        assert_eq!(path, vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+    }
+
+    #[test]
+    fn find_path_astar_prefers_diagonal() {
+        let g = build_empty_tile_graph(3, 3);
+        let (cost, path) = find_path_astar(&g, (0, 0), (2, 2)).unwrap();
+        assert_eq!(cost, 2 * DIAGONAL_COST);
+        assert_eq!(path, vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn find_path_astar_stays_admissible_on_cheap_weighted_terrain() {
+        // A direct but pricier chain (weight 2 per step) competes with a
+        // cheaper detour (weight 1 per step) that strays further from the
+        // goal in grid coordinates. A heuristic hard-coded to
+        // ORTHOGONAL_COST/DIAGONAL_COST overestimates the detour's true
+        // remaining cost so heavily that A* pops the goal via the pricier
+        // chain (cost 8) before ever exploring the cheaper one (cost 5).
+        let mut g = UnGraphMap::<Point, i32>::new();
+        let direct = [(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)];
+        for w in direct.windows(2) {
+            g.add_edge(w[0], w[1], 2);
+        }
+        let detour = [(0, 0), (1, 1), (2, 1), (3, 1), (4, 1), (4, 0)];
+        for w in detour.windows(2) {
+            g.add_edge(w[0], w[1], 1);
+        }
+
+        let start = (0, 0);
+        let goal = (4, 0);
+        let (astar_cost, _) = find_path_astar(&g, start, goal).unwrap();
+        let dijkstra_dist = dijkstra(&g, start, Some(goal), |(_, _, &w)| w);
+        assert_eq!(Some(astar_cost), dijkstra_dist.get(&goal).copied());
+    }
+
+    #[test]
+    fn find_path_astar_no_path_for_unknown_goal() {
+        let g = build_empty_tile_graph(2, 2);
+        assert_eq!(find_path_astar(&g, (0, 0), (5, 5)), None);
+    }
+
     #[test]
     fn neighbors_all_around() {
         let p = (1, 1);
@@ -236,4 +756,308 @@ mod path_finding {
         assert!(actual.contains_node((0, 1)));
         assert!(actual.contains_edge((0, 1), (0, 0)));
     }
+
+    #[test]
+    fn weighted_tile_graph_uses_per_tile_cost() {
+        let actual = build_weighted_tile_graph(1, 2, |_| Some(1));
+        assert_eq!(actual.node_count(), 2);
+        assert_eq!(actual.edge_count(), 1);
+        assert_eq!(
+            actual.edge_weight((0, 0), (0, 1)),
+            Some(&ORTHOGONAL_COST)
+        );
+    }
+
+    #[test]
+    fn weighted_tile_graph_omits_blocked_tiles() {
+        let actual = build_weighted_tile_graph(2, 2, |p| {
+            if p == (1, 1) {
+                None
+            } else {
+                Some(ORTHOGONAL_COST)
+            }
+        });
+        assert_eq!(actual.node_count(), 3);
+        assert!(!actual.contains_node((1, 1)));
+        assert!(!actual.contains_edge((0, 1), (1, 1)));
+        assert!(actual.contains_edge((0, 0), (0, 1)));
+    }
+
+    #[test]
+    fn weighted_tile_graph_scales_diagonal_moves() {
+        let actual = build_weighted_tile_graph(2, 2, |_| Some(1));
+        assert_eq!(
+            actual.edge_weight((0, 0), (1, 1)),
+            Some(&DIAGONAL_COST)
+        );
+        assert_eq!(
+            actual.edge_weight((0, 0), (1, 0)),
+            Some(&ORTHOGONAL_COST)
+        );
+    }
+
+    #[should_panic]
+    #[test]
+    fn weighted_tile_graph_size_x_lt_1() {
+        build_weighted_tile_graph(0, 1, |_| Some(1));
+    }
+
+    #[test]
+    fn supercover_line_horizontal() {
+        assert_eq!(
+            supercover_line((0, 0), (3, 0)),
+            vec![(0, 0), (1, 0), (2, 0), (3, 0)]
+        );
+    }
+
+    #[test]
+    fn supercover_line_vertical() {
+        assert_eq!(
+            supercover_line((0, 3), (0, 0)),
+            vec![(0, 3), (0, 2), (0, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn supercover_line_right_to_left_diagonal() {
+        assert_eq!(
+            supercover_line((2, 2), (0, 0)),
+            vec![(2, 2), (1, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn supercover_line_same_point() {
+        assert_eq!(supercover_line((1, 1), (1, 1)), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn supercover_line_touches_both_cells_across_a_row_boundary() {
+        // Slope 1:2 from (0, 0) to (2, 1) crosses from row 0 into row 1 at
+        // (1, 0.5), on the boundary between cells (1, 0) and (1, 1), so a true
+        // supercover walk must include both (1, 0) and (1, 1).
+        let touched = supercover_line((0, 0), (2, 1));
+        assert!(touched.contains(&(1, 0)));
+        assert!(touched.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn line_of_sight_clear_on_open_grid() {
+        let g = build_empty_tile_graph(4, 4);
+        assert!(line_of_sight(&g, (0, 0), (3, 3)));
+    }
+
+    #[test]
+    fn line_of_sight_blocked_by_missing_tile() {
+        let g = build_weighted_tile_graph(3, 1, |p| if p == (1, 0) { None } else { Some(1) });
+        assert!(!line_of_sight(&g, (0, 0), (2, 0)));
+    }
+
+    #[test]
+    fn find_path_theta_star_shortcuts_straight_line() {
+        let g = build_empty_tile_graph(5, 5);
+        let (cost, path) = find_path_theta_star(&g, (0, 0), (4, 0)).unwrap();
+        assert_eq!(path, vec![(0, 0), (4, 0)]);
+        assert_eq!(cost, 4.0 * ORTHOGONAL_COST as f64);
+    }
+
+    #[test]
+    fn find_path_theta_star_routes_around_a_wall() {
+        let g = build_weighted_tile_graph(3, 3, |p| if p == (1, 1) { None } else { Some(10) });
+        let (_, path) = find_path_theta_star(&g, (0, 0), (2, 2)).unwrap();
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(2, 2)));
+        assert!(!path.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn find_path_theta_star_uses_graph_edge_weight_without_line_of_sight() {
+        // (1, 0) to (3, 1) has no line of sight (the grid has no tiles along
+        // its supercover line), so this hop takes the fallback branch, which
+        // must cost it as the graph's actual edge weight rather than
+        // Euclidean distance, to stay consistent with the line-of-sight
+        // branch's real per-tile cost.
+        let mut g = UnGraphMap::<Point, i32>::new();
+        g.add_edge((0, 0), (1, 0), 1);
+        g.add_edge((1, 0), (3, 1), 7);
+        let (cost, path) = find_path_theta_star(&g, (0, 0), (3, 1)).unwrap();
+        assert_eq!(path, vec![(0, 0), (1, 0), (3, 1)]);
+        assert_eq!(cost, 1.0 + 7.0);
+    }
+
+    #[test]
+    fn find_path_theta_star_reports_the_real_cost_of_its_returned_path() {
+        // On terrain where a straight row (y = 0) is far pricier per tile
+        // than the row below it, a line-of-sight shortcut that costs itself
+        // as Euclidean distance would silently ignore that and report a
+        // cost with no relation to what the returned path actually costs to
+        // walk. The reported cost must always equal the sum of real
+        // per-tile cost along every hop of the returned path (walking each
+        // turn-point-to-turn-point leg as its own line-of-sight cost).
+        let g = build_weighted_tile_graph(5, 2, |p| if p.1 == 0 { Some(100) } else { Some(1) });
+        let (cost, path) = find_path_theta_star(&g, (0, 0), (4, 0)).unwrap();
+        let true_cost: f64 = path
+            .windows(2)
+            .map(|leg| line_of_sight_cost(&g, leg[0], leg[1]).unwrap())
+            .sum();
+        assert_eq!(cost, true_cost);
+    }
+
+    #[test]
+    fn find_path_theta_star_start_equals_goal() {
+        let g = build_empty_tile_graph(2, 2);
+        let (cost, path) = find_path_theta_star(&g, (0, 0), (0, 0)).unwrap();
+        assert_eq!(cost, 0.0);
+        assert_eq!(path, vec![(0, 0)]);
+    }
+
+    #[test]
+    fn all_shortest_paths_finds_every_optimal_route() {
+        // A 1x3 strip has exactly one shortest path between its ends.
+        let g = build_empty_tile_graph(3, 1);
+        let paths = all_shortest_paths(&g, (0, 0), (2, 0), None);
+        assert_eq!(paths, vec![vec![(0, 0), (1, 0), (2, 0)]]);
+    }
+
+    fn diamond_graph() -> UnGraphMap<Point, i32> {
+        // (0,0) -- 1 --> (1,0) -- 1 --> (2,0)
+        //   \------------- 1 --> (1,1) -- 1 --/
+        // Two equally-cheap routes from (0,0) to (2,0).
+        let mut g = UnGraphMap::<Point, i32>::new();
+        g.add_edge((0, 0), (1, 0), 1);
+        g.add_edge((1, 0), (2, 0), 1);
+        g.add_edge((0, 0), (1, 1), 1);
+        g.add_edge((1, 1), (2, 0), 1);
+        g
+    }
+
+    #[test]
+    fn all_shortest_paths_finds_both_equal_cost_routes() {
+        let mut paths = all_shortest_paths(&diamond_graph(), (0, 0), (2, 0), None);
+        paths.sort();
+        let mut expected = vec![
+            vec![(0, 0), (1, 0), (2, 0)],
+            vec![(0, 0), (1, 1), (2, 0)],
+        ];
+        expected.sort();
+        assert_eq!(paths, expected);
+    }
+
+    #[test]
+    fn all_shortest_paths_respects_cap() {
+        let paths = all_shortest_paths(&diamond_graph(), (0, 0), (2, 0), Some(1));
+        assert_eq!(paths.len(), 1);
+    }
+
+    #[test]
+    fn all_shortest_paths_terminates_on_zero_weight_edges() {
+        // Every edge is tight in both directions when its weight is 0, so
+        // the predecessor "DAG" is really a 2-cycle here; without a
+        // visited-path guard the backtrack recurses forever.
+        let g = build_weighted_tile_graph(3, 1, |_| Some(0));
+        let paths = all_shortest_paths(&g, (0, 0), (2, 0), None);
+        assert_eq!(paths, vec![vec![(0, 0), (1, 0), (2, 0)]]);
+    }
+
+    #[test]
+    fn all_shortest_paths_no_path_returns_empty() {
+        let g = build_weighted_tile_graph(2, 1, |p| if p == (1, 0) { None } else { Some(1) });
+        assert_eq!(all_shortest_paths(&g, (0, 0), (1, 0), None), Vec::<Vec<Point>>::new());
+    }
+
+    #[test]
+    fn all_shortest_paths_start_equals_goal() {
+        let g = build_empty_tile_graph(2, 2);
+        assert_eq!(
+            all_shortest_paths(&g, (0, 0), (0, 0), None),
+            vec![vec![(0, 0)]]
+        );
+    }
+
+    #[test]
+    fn to_csr_benchmark() {
+        // Building via Csr::from_sorted_edges should scale with graph size
+        // rather than blow up superlinearly the way incremental add_edge did.
+        for graph_size_power in 6..9 {
+            let base: i32 = 2;
+            let graph_size = base.pow(graph_size_power);
+            let house: UnGraphMap<Point, i32> = build_empty_tile_graph(graph_size, graph_size);
+            let now = Instant::now();
+            to_csr(&house);
+            println!("graph size: {}, milliseconds elapsed: {}", graph_size, now.elapsed().as_millis());
+        }
+    }
+
+    #[test]
+    fn to_csr_preserves_node_and_edge_counts() {
+        let g = build_empty_tile_graph(3, 3);
+        let (csr, index_to_point, point_to_index) = to_csr(&g);
+        assert_eq!(csr.node_count(), g.node_count());
+        // The CSR is Directed and lists both directions of every undirected
+        // edge explicitly, so its edge count is double the original's.
+        assert_eq!(csr.edge_count(), g.edge_count() * 2);
+        assert_eq!(index_to_point.len(), point_to_index.len());
+        for (i, &p) in index_to_point.iter().enumerate() {
+            assert_eq!(point_to_index[&p], i as u32);
+        }
+    }
+
+    #[test]
+    fn find_path_astar_csr_matches_find_path_astar() {
+        let g = build_empty_tile_graph(8, 8);
+        let (csr, index_to_point, point_to_index) = to_csr(&g);
+        let expected = find_path_astar(&g, (0, 0), (7, 7)).unwrap();
+        let actual =
+            find_path_astar_csr(&csr, &index_to_point, &point_to_index, (0, 0), (7, 7)).unwrap();
+        assert_eq!(actual.0, expected.0);
+        assert_eq!(actual.1, expected.1);
+    }
+
+    #[test]
+    fn find_path_astar_csr_unknown_point_returns_none() {
+        let g = build_empty_tile_graph(2, 2);
+        let (csr, index_to_point, point_to_index) = to_csr(&g);
+        assert_eq!(
+            find_path_astar_csr(&csr, &index_to_point, &point_to_index, (0, 0), (9, 9)),
+            None
+        );
+    }
+
+    #[test]
+    fn terrain_graph_blocks_climbing_past_the_limit_but_allows_falling() {
+        // A one-step cliff from (0, 0) up to (1, 0): climbing it exceeds
+        // max_climb, but stepping back down is still allowed.
+        let g = build_terrain_graph(2, 1, |p| if p == (1, 0) { 2 } else { 0 }, 1, |_| 0);
+        assert!(!g.contains_edge((0, 0), (1, 0)));
+        assert!(g.contains_edge((1, 0), (0, 0)));
+    }
+
+    #[test]
+    fn terrain_graph_allows_climbs_within_the_limit() {
+        let g = build_terrain_graph(2, 1, |p| if p == (1, 0) { 1 } else { 0 }, 1, |_| 0);
+        assert!(g.contains_edge((0, 0), (1, 0)));
+        assert!(g.contains_edge((1, 0), (0, 0)));
+    }
+
+    #[test]
+    fn terrain_graph_adds_slope_penalty_to_horizontal_cost() {
+        let g = build_terrain_graph(
+            2,
+            1,
+            |p| if p == (1, 0) { 1 } else { 0 },
+            1,
+            |climb| climb * 5,
+        );
+        assert_eq!(
+            g.edge_weight((0, 0), (1, 0)),
+            Some(&(ORTHOGONAL_COST + 5))
+        );
+        assert_eq!(g.edge_weight((1, 0), (0, 0)), Some(&ORTHOGONAL_COST));
+    }
+
+    #[should_panic]
+    #[test]
+    fn terrain_graph_size_x_lt_1() {
+        build_terrain_graph(0, 1, |_| 0, 0, |_| 0);
+    }
 }